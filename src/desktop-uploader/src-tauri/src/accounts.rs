@@ -0,0 +1,224 @@
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::AppHandle;
+
+use crate::credentials::{self, StoredCredentials};
+use crate::secure_store::{self, SecureStoreError};
+use crate::state::{self, ActiveSession, AppState};
+
+const TOKEN_SERVICE: &str = "FrameFast";
+const ACCOUNT_INDEX_ACCOUNT: &str = "account_index";
+
+/// One entry in the account switcher index. Mirrors what the frontend
+/// needs to render a list of logged-in accounts without touching the
+/// keyring for every row.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountSummary {
+    pub id: String,
+    pub display_name: String,
+    pub last_used: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct AccountIndex {
+    accounts: Vec<AccountSummary>,
+}
+
+fn now_unix() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn load_index(app: &AppHandle) -> Result<AccountIndex, String> {
+    let raw = secure_store::platform_store(app)
+        .get(TOKEN_SERVICE, ACCOUNT_INDEX_ACCOUNT)
+        .map_err(String::from)?;
+    match raw {
+        Some(raw) => serde_json::from_str(&raw).map_err(|e| e.to_string()),
+        None => Ok(AccountIndex::default()),
+    }
+}
+
+fn save_index(app: &AppHandle, index: &AccountIndex) -> Result<(), String> {
+    let raw = serde_json::to_string(index).map_err(|e| e.to_string())?;
+    secure_store::platform_store(app)
+        .set(TOKEN_SERVICE, ACCOUNT_INDEX_ACCOUNT, &raw)
+        .map_err(String::from)
+}
+
+/// Update (or insert) an account's entry in the index and bump its
+/// `last_used` timestamp, returning the resulting summary.
+fn touch_account(app: &AppHandle, id: &str, display_name: &str) -> Result<AccountSummary, String> {
+    let mut index = load_index(app)?;
+    let now = now_unix();
+    let summary = match index.accounts.iter_mut().find(|a| a.id == id) {
+        Some(account) => {
+            account.display_name = display_name.to_string();
+            account.last_used = now;
+            account.clone()
+        }
+        None => {
+            let summary = AccountSummary {
+                id: id.to_string(),
+                display_name: display_name.to_string(),
+                last_used: now,
+            };
+            index.accounts.push(summary.clone());
+            summary
+        }
+    };
+    save_index(app, &index)?;
+    Ok(summary)
+}
+
+/// Store credentials for an account and update the switcher index. Used
+/// internally by flows (like OAuth) that have more than a bare token to
+/// persist; frontend callers go through the `set_auth_token` command.
+pub fn set_credentials(
+    app: &AppHandle,
+    account_id: &str,
+    display_name: &str,
+    creds: &StoredCredentials,
+) -> Result<AccountSummary, String> {
+    credentials::store(app, account_id, creds).map_err(String::from)?;
+    touch_account(app, account_id, display_name)
+}
+
+/// Raw accessor kept for backward compatibility: stores a bare token with
+/// no expiry or refresh metadata. Also becomes the active in-memory
+/// session and notifies the frontend via `auth-changed`.
+///
+/// Returns the typed `SecureStoreError` (rather than a string) so the
+/// frontend can tell "no secure backend on this platform" apart from a
+/// backend-specific failure.
+#[tauri::command]
+pub fn set_auth_token(
+    account_id: String,
+    display_name: String,
+    token: String,
+    app: AppHandle,
+    app_state: tauri::State<'_, AppState>,
+) -> Result<(), SecureStoreError> {
+    let creds = StoredCredentials::bare(token);
+    credentials::store(&app, &account_id, &creds)?;
+    let account =
+        touch_account(&app, &account_id, &display_name).map_err(SecureStoreError::Backend)?;
+    let session = ActiveSession {
+        account,
+        credentials: creds,
+    };
+    app_state.set(Some(session.clone()));
+    state::emit_auth_changed(&app, Some(&session));
+    Ok(())
+}
+
+/// Raw accessor kept for backward compatibility: returns whatever access
+/// token is on file without checking expiry. Use `get_valid_token` when
+/// the caller needs a token that's guaranteed not to be stale.
+#[tauri::command]
+pub fn get_auth_token(
+    account_id: String,
+    app: AppHandle,
+) -> Result<Option<String>, SecureStoreError> {
+    Ok(credentials::load(&app, &account_id)?.map(|creds| creds.access_token))
+}
+
+#[tauri::command]
+pub fn clear_auth_token(
+    account_id: String,
+    app: AppHandle,
+    app_state: tauri::State<'_, AppState>,
+) -> Result<(), SecureStoreError> {
+    credentials::delete(&app, &account_id)?;
+
+    let mut index = load_index(&app).map_err(SecureStoreError::Backend)?;
+    index.accounts.retain(|a| a.id != account_id);
+    save_index(&app, &index).map_err(SecureStoreError::Backend)?;
+
+    if app_state
+        .get()
+        .is_some_and(|session| session.account.id == account_id)
+    {
+        app_state.set(None);
+        state::emit_auth_changed(&app, None);
+    }
+    Ok(())
+}
+
+/// List known accounts, most recently used first, for the account switcher.
+#[tauri::command]
+pub fn list_accounts(app: AppHandle) -> Result<Vec<AccountSummary>, String> {
+    let mut index = load_index(&app)?;
+    index
+        .accounts
+        .sort_by(|a, b| b.last_used.cmp(&a.last_used));
+    Ok(index.accounts)
+}
+
+/// Load the stored credentials for an account, bump its `last_used`
+/// timestamp, and make it the active in-memory session — switching
+/// accounts through the switcher should behave like logging into that
+/// account, so `is_logged_in`/`auth-changed` reflect it immediately.
+#[tauri::command]
+pub fn load_account(
+    account_id: String,
+    app: AppHandle,
+    app_state: tauri::State<'_, AppState>,
+) -> Result<Option<String>, String> {
+    let Some(credentials) = credentials::load(&app, &account_id).map_err(String::from)? else {
+        return Ok(None);
+    };
+
+    let display_name = load_index(&app)?
+        .accounts
+        .into_iter()
+        .find(|a| a.id == account_id)
+        .map(|a| a.display_name)
+        .unwrap_or_else(|| account_id.clone());
+    let account = touch_account(&app, &account_id, &display_name)?;
+
+    let token = credentials.access_token.clone();
+    let session = ActiveSession { account, credentials };
+    app_state.set(Some(session.clone()));
+    state::emit_auth_changed(&app, Some(&session));
+
+    Ok(Some(token))
+}
+
+/// Mark an account as the active one: bump its `last_used` timestamp and
+/// load its credentials into the in-memory session, notifying the
+/// frontend via `auth-changed` the same way logging in does.
+#[tauri::command]
+pub fn set_active_account(
+    account_id: String,
+    app: AppHandle,
+    app_state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    let display_name = load_index(&app)?
+        .accounts
+        .into_iter()
+        .find(|a| a.id == account_id)
+        .map(|a| a.display_name)
+        .ok_or_else(|| format!("unknown account: {account_id}"))?;
+    let account = touch_account(&app, &account_id, &display_name)?;
+
+    let credentials = credentials::load(&app, &account_id)
+        .map_err(String::from)?
+        .ok_or_else(|| format!("no credentials stored for account: {account_id}"))?;
+
+    let session = ActiveSession { account, credentials };
+    app_state.set(Some(session.clone()));
+    state::emit_auth_changed(&app, Some(&session));
+    Ok(())
+}
+
+#[tauri::command]
+pub fn remove_account(
+    account_id: String,
+    app: AppHandle,
+    app_state: tauri::State<'_, AppState>,
+) -> Result<(), SecureStoreError> {
+    clear_auth_token(account_id, app, app_state)
+}
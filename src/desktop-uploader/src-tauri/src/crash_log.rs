@@ -0,0 +1,58 @@
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const APP_NAME: &str = "FrameFast";
+
+/// Install a panic hook that writes a timestamped crash log to the app's
+/// log directory, on top of what the default hook already does. The panic
+/// is also reported through the `log` crate, so `tauri_plugin_log` records
+/// it once it's attached.
+///
+/// This must be installed before the builder runs: keyring access and
+/// OAuth setup happen during `setup`, and a panic there would otherwise
+/// be lost since `tauri_plugin_log` isn't attached yet. Unlike the log
+/// plugin, this isn't gated on `debug_assertions` so release builds still
+/// leave a file behind for bug reports.
+pub fn install() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        default_hook(info);
+        log::error!("{info}");
+        write_crash_log(info);
+    }));
+}
+
+fn write_crash_log(info: &std::panic::PanicInfo) {
+    let Some(dir) = log_dir() else { return };
+    if fs::create_dir_all(&dir).is_err() {
+        return;
+    }
+
+    let path = dir.join(format!("crash-{}.log", now_unix()));
+    let Ok(mut file) = fs::File::create(&path) else {
+        return;
+    };
+
+    let _ = writeln!(file, "{APP_NAME} crash report");
+    let _ = writeln!(file, "version: {}", env!("CARGO_PKG_VERSION"));
+    let _ = writeln!(file, "os: {}", std::env::consts::OS);
+    let _ = writeln!(file, "timestamp: {}", now_unix());
+    let _ = writeln!(file);
+    let _ = writeln!(file, "{info}");
+    let _ = writeln!(file);
+    let _ = writeln!(file, "backtrace:");
+    let _ = writeln!(file, "{}", std::backtrace::Backtrace::force_capture());
+}
+
+fn log_dir() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|dir| dir.join(APP_NAME).join("logs"))
+}
+
+fn now_unix() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
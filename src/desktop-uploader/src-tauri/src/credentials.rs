@@ -0,0 +1,265 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::AppHandle;
+use tokio::sync::Mutex as AsyncMutex;
+
+use crate::secure_store::{self, SecureStoreError};
+use crate::state::AppState;
+
+const TOKEN_SERVICE: &str = "FrameFast";
+
+/// Default skew window, in seconds, before `expires_at` at which a token is
+/// considered due for refresh rather than waiting for it to actually lapse.
+/// Used when a `RefreshConfig` doesn't specify its own `refresh_skew_secs`.
+const DEFAULT_REFRESH_SKEW_SECS: u64 = 60;
+
+/// What's needed to refresh an account's token without the frontend
+/// having to resend provider config on every call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefreshConfig {
+    pub token_url: String,
+    pub client_id: String,
+    pub client_secret: Option<String>,
+    /// Skew window, in seconds, before `expires_at` at which the token is
+    /// considered due for refresh. Falls back to `DEFAULT_REFRESH_SKEW_SECS`
+    /// when not set, so existing stored credentials without this field
+    /// still deserialize and behave the same as before.
+    #[serde(default)]
+    pub refresh_skew_secs: Option<u64>,
+}
+
+/// The full payload stored in keyring for one account, replacing the old
+/// bare token string so expiry can be tracked and refreshed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredCredentials {
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+    pub expires_at: Option<u64>,
+    pub token_type: Option<String>,
+    pub refresh_config: Option<RefreshConfig>,
+}
+
+impl StoredCredentials {
+    pub fn bare(access_token: String) -> Self {
+        Self {
+            access_token,
+            refresh_token: None,
+            expires_at: None,
+            token_type: None,
+            refresh_config: None,
+        }
+    }
+
+    fn needs_refresh(&self) -> bool {
+        match (self.expires_at, &self.refresh_token) {
+            (Some(expires_at), Some(_)) => {
+                let skew = self
+                    .refresh_config
+                    .as_ref()
+                    .and_then(|config| config.refresh_skew_secs)
+                    .unwrap_or(DEFAULT_REFRESH_SKEW_SECS);
+                now_unix().saturating_add(skew) >= expires_at
+            }
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[serde(default)]
+    refresh_token: Option<String>,
+    #[serde(default)]
+    expires_in: Option<u64>,
+    #[serde(default)]
+    token_type: Option<String>,
+}
+
+fn now_unix() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn token_account_key(id: &str) -> String {
+    format!("token:{id}")
+}
+
+/// Persist credentials for an account. Returns the typed `SecureStoreError`
+/// as-is (rather than collapsing it to a string) so callers can tell "no
+/// backend compiled in" apart from a backend-specific failure.
+pub fn store(
+    app: &AppHandle,
+    account_id: &str,
+    creds: &StoredCredentials,
+) -> Result<(), SecureStoreError> {
+    let raw = serde_json::to_string(creds).map_err(|e| SecureStoreError::Backend(e.to_string()))?;
+    secure_store::platform_store(app).set(TOKEN_SERVICE, &token_account_key(account_id), &raw)
+}
+
+pub fn load(
+    app: &AppHandle,
+    account_id: &str,
+) -> Result<Option<StoredCredentials>, SecureStoreError> {
+    let raw = secure_store::platform_store(app).get(TOKEN_SERVICE, &token_account_key(account_id))?;
+    raw.map(|raw| serde_json::from_str(&raw).map_err(|e| SecureStoreError::Backend(e.to_string())))
+        .transpose()
+}
+
+pub fn delete(app: &AppHandle, account_id: &str) -> Result<(), SecureStoreError> {
+    secure_store::platform_store(app).delete(TOKEN_SERVICE, &token_account_key(account_id))
+}
+
+async fn refresh(creds: &StoredCredentials) -> Result<StoredCredentials, String> {
+    let refresh_token = creds
+        .refresh_token
+        .as_ref()
+        .ok_or("no refresh token available")?;
+    let config = creds
+        .refresh_config
+        .as_ref()
+        .ok_or("no refresh config available")?;
+
+    let mut form = vec![
+        ("grant_type", "refresh_token"),
+        ("refresh_token", refresh_token.as_str()),
+        ("client_id", config.client_id.as_str()),
+    ];
+    if let Some(secret) = &config.client_secret {
+        form.push(("client_secret", secret));
+    }
+
+    let response: TokenResponse = reqwest::Client::new()
+        .post(&config.token_url)
+        .form(&form)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?
+        .error_for_status()
+        .map_err(|e| e.to_string())?
+        .json()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(StoredCredentials {
+        access_token: response.access_token,
+        refresh_token: response.refresh_token.or_else(|| creds.refresh_token.clone()),
+        expires_at: response.expires_in.map(|secs| now_unix() + secs),
+        token_type: response.token_type.or_else(|| creds.token_type.clone()),
+        refresh_config: creds.refresh_config.clone(),
+    })
+}
+
+/// Managed state that serializes concurrent refresh attempts per account,
+/// so two frontend calls racing on an expired token don't both hit the
+/// refresh endpoint.
+#[derive(Default)]
+pub struct RefreshState {
+    locks: StdMutex<HashMap<String, Arc<AsyncMutex<()>>>>,
+}
+
+impl RefreshState {
+    fn lock_for(&self, account_id: &str) -> Arc<AsyncMutex<()>> {
+        self.locks
+            .lock()
+            .unwrap()
+            .entry(account_id.to_string())
+            .or_insert_with(|| Arc::new(AsyncMutex::new(())))
+            .clone()
+    }
+}
+
+/// Return a valid access token for `account_id`, transparently refreshing
+/// it first if it's within the skew window of expiry and a refresh token
+/// is available. Keeps `AppState`'s cached session in sync with the
+/// refreshed credentials when `account_id` is the active session, the
+/// same way `load_account`/`set_active_account`/`start_oauth_login` do.
+#[tauri::command]
+pub async fn get_valid_token(
+    app: AppHandle,
+    state: tauri::State<'_, RefreshState>,
+    app_state: tauri::State<'_, AppState>,
+    account_id: String,
+) -> Result<String, String> {
+    let lock = state.lock_for(&account_id);
+    let _guard = lock.lock().await;
+
+    let creds = load(&app, &account_id)
+        .map_err(String::from)?
+        .ok_or("no credentials stored for account")?;
+    if !creds.needs_refresh() {
+        return Ok(creds.access_token);
+    }
+
+    let refreshed = refresh(&creds).await?;
+    store(&app, &account_id, &refreshed).map_err(String::from)?;
+
+    if let Some(mut session) = app_state.get() {
+        if session.account.id == account_id {
+            session.credentials = refreshed.clone();
+            app_state.set(Some(session));
+        }
+    }
+
+    Ok(refreshed.access_token)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn creds_expiring_in(secs_from_now: i64, skew: Option<u64>) -> StoredCredentials {
+        let expires_at = now_unix().saturating_add_signed(secs_from_now);
+        StoredCredentials {
+            access_token: "access".to_string(),
+            refresh_token: Some("refresh".to_string()),
+            expires_at: Some(expires_at),
+            token_type: None,
+            refresh_config: Some(RefreshConfig {
+                token_url: "https://provider.example/token".to_string(),
+                client_id: "client".to_string(),
+                client_secret: None,
+                refresh_skew_secs: skew,
+            }),
+        }
+    }
+
+    #[test]
+    fn needs_refresh_false_well_before_expiry() {
+        let creds = creds_expiring_in(3600, None);
+        assert!(!creds.needs_refresh());
+    }
+
+    #[test]
+    fn needs_refresh_true_inside_default_skew_window() {
+        let creds = creds_expiring_in(30, None);
+        assert!(creds.needs_refresh());
+    }
+
+    #[test]
+    fn needs_refresh_respects_configured_skew() {
+        let creds = creds_expiring_in(90, Some(120));
+        assert!(creds.needs_refresh());
+
+        let creds = creds_expiring_in(90, Some(10));
+        assert!(!creds.needs_refresh());
+    }
+
+    #[test]
+    fn needs_refresh_false_without_refresh_token() {
+        let mut creds = creds_expiring_in(0, None);
+        creds.refresh_token = None;
+        assert!(!creds.needs_refresh());
+    }
+
+    #[test]
+    fn needs_refresh_false_without_expiry() {
+        let mut creds = creds_expiring_in(0, None);
+        creds.expires_at = None;
+        assert!(!creds.needs_refresh());
+    }
+}
@@ -1,12 +1,43 @@
+mod accounts;
+mod crash_log;
+mod credentials;
+mod oauth;
+mod secure_store;
+mod state;
+
+use accounts::{
+    clear_auth_token, get_auth_token, list_accounts, load_account, remove_account,
+    set_active_account, set_auth_token,
+};
+use credentials::{get_valid_token, RefreshState};
+use oauth::start_oauth_login;
+use state::{is_logged_in, AppState};
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    tauri::Builder::default()
+    crash_log::install();
+
+    let builder = tauri::Builder::default()
         .plugin(tauri_plugin_oauth::init())
-        .plugin(tauri_plugin_shell::init())
+        .plugin(tauri_plugin_shell::init());
+
+    #[cfg(mobile)]
+    let builder = builder.plugin(tauri_plugin_secure_storage::init());
+
+    builder
+        .manage(RefreshState::default())
+        .manage(AppState::default())
         .invoke_handler(tauri::generate_handler![
             set_auth_token,
             get_auth_token,
-            clear_auth_token
+            clear_auth_token,
+            list_accounts,
+            load_account,
+            set_active_account,
+            remove_account,
+            start_oauth_login,
+            get_valid_token,
+            is_logged_in
         ])
         .setup(|app| {
             if cfg!(debug_assertions) {
@@ -16,38 +47,9 @@ pub fn run() {
                         .build(),
                 )?;
             }
+            state::hydrate(app.handle())?;
             Ok(())
         })
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
-
-const TOKEN_SERVICE: &str = "FrameFast";
-const TOKEN_ACCOUNT: &str = "auth_token";
-
-#[tauri::command]
-fn set_auth_token(token: String) -> Result<(), String> {
-    let entry = keyring::Entry::new(TOKEN_SERVICE, TOKEN_ACCOUNT).map_err(|e| e.to_string())?;
-    entry.set_password(&token).map_err(|e| e.to_string())?;
-    Ok(())
-}
-
-#[tauri::command]
-fn get_auth_token() -> Result<Option<String>, String> {
-    let entry = keyring::Entry::new(TOKEN_SERVICE, TOKEN_ACCOUNT).map_err(|e| e.to_string())?;
-    match entry.get_password() {
-        Ok(token) => Ok(Some(token)),
-        Err(keyring::Error::NoEntry) => Ok(None),
-        Err(err) => Err(err.to_string()),
-    }
-}
-
-#[tauri::command]
-fn clear_auth_token() -> Result<(), String> {
-    let entry = keyring::Entry::new(TOKEN_SERVICE, TOKEN_ACCOUNT).map_err(|e| e.to_string())?;
-    match entry.delete_password() {
-        Ok(_) => Ok(()),
-        Err(keyring::Error::NoEntry) => Ok(()),
-        Err(err) => Err(err.to_string()),
-    }
-}
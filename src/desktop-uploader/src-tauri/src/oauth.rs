@@ -0,0 +1,374 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_shell::ShellExt;
+use tokio::sync::oneshot;
+
+use crate::accounts::{self, AccountSummary};
+use crate::credentials::{RefreshConfig, StoredCredentials};
+use crate::state::{self, ActiveSession, AppState};
+
+const OAUTH_PROGRESS_EVENT: &str = "oauth-progress";
+
+fn now_unix() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Everything needed to drive one OAuth authorization-code round trip.
+/// The frontend supplies this per login provider (e.g. Google, GitHub).
+///
+/// `userinfo_url` is required (rather than optional) because the account
+/// id this flow stores under has to identify the *user*, not the OAuth
+/// client: falling back to `client_id` when it's absent would collapse
+/// every user of that provider config into one stored account.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OAuthProviderConfig {
+    pub client_id: String,
+    pub client_secret: Option<String>,
+    pub auth_url: String,
+    pub token_url: String,
+    pub userinfo_url: String,
+    pub scope: Option<String>,
+    /// Refresh skew window in seconds, forwarded into the stored
+    /// `RefreshConfig`. See `credentials::RefreshConfig::refresh_skew_secs`.
+    #[serde(default)]
+    pub refresh_skew_secs: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[serde(default)]
+    refresh_token: Option<String>,
+    #[serde(default)]
+    expires_in: Option<u64>,
+    #[serde(default)]
+    token_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UserInfoResponse {
+    #[serde(alias = "sub", alias = "id")]
+    id: String,
+    #[serde(default, alias = "name", alias = "email")]
+    display_name: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+struct OAuthProgress<'a> {
+    stage: &'a str,
+}
+
+fn emit_progress(app: &AppHandle, stage: &str) {
+    let _ = app.emit(OAUTH_PROGRESS_EVENT, OAuthProgress { stage });
+}
+
+/// One query parameter's worth of randomness, base64url-encoded with no
+/// padding so it drops straight into a URL — used for both the PKCE code
+/// verifier and the CSRF `state` value.
+fn random_url_safe_token(byte_len: usize) -> String {
+    let mut bytes = vec![0u8; byte_len];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// Derive the PKCE `code_challenge` (S256) for a given `code_verifier`,
+/// per RFC 7636.
+fn pkce_code_challenge(code_verifier: &str) -> String {
+    let digest = Sha256::digest(code_verifier.as_bytes());
+    URL_SAFE_NO_PAD.encode(digest)
+}
+
+/// The authorization code and `state` value pulled off the localhost
+/// redirect. `state` is optional at the parse level only so a missing
+/// value can be reported as "state mismatch" like any other wrong value,
+/// rather than a separate parse error.
+struct RedirectParams {
+    code: String,
+    state: Option<String>,
+}
+
+/// Run an OAuth authorization-code flow end to end: open the system
+/// browser at `auth_url`, wait for the localhost redirect carrying the
+/// authorization code, exchange it for a token, and persist the result
+/// through the keyring-backed account store. Returns the resolved
+/// account identity so the frontend can update the switcher.
+///
+/// Uses PKCE (RFC 7636) and a CSRF `state` value, as required for a
+/// loopback-redirect native-app flow per RFC 8252.
+#[tauri::command]
+pub async fn start_oauth_login(
+    app: AppHandle,
+    app_state: tauri::State<'_, AppState>,
+    provider_config: OAuthProviderConfig,
+) -> Result<AccountSummary, String> {
+    emit_progress(&app, "listening");
+
+    let code_verifier = random_url_safe_token(32);
+    let csrf_state = random_url_safe_token(16);
+
+    let (code_tx, code_rx) = oneshot::channel::<Result<RedirectParams, String>>();
+    let code_tx = std::sync::Mutex::new(Some(code_tx));
+
+    let port = tauri_plugin_oauth::start(move |url| {
+        if let Some(tx) = code_tx.lock().unwrap().take() {
+            let _ = tx.send(extract_redirect_params(&url));
+        }
+    })
+    .map_err(|e| e.to_string())?;
+
+    // Run the rest of the flow behind the listener, then always tear the
+    // listener down afterward regardless of how the flow finished —
+    // success or any error. The wait for the redirect has no timeout: if
+    // no redirect ever arrives, the flow does not finish and the listener
+    // stays up.
+    let result = run_login(
+        &app,
+        &app_state,
+        &provider_config,
+        port,
+        code_rx,
+        &code_verifier,
+        &csrf_state,
+    )
+    .await;
+    let _ = tauri_plugin_oauth::cancel(port);
+    result
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn run_login(
+    app: &AppHandle,
+    app_state: &tauri::State<'_, AppState>,
+    provider_config: &OAuthProviderConfig,
+    port: u16,
+    code_rx: oneshot::Receiver<Result<RedirectParams, String>>,
+    code_verifier: &str,
+    csrf_state: &str,
+) -> Result<AccountSummary, String> {
+    let redirect_uri = format!("http://localhost:{port}");
+    let code_challenge = pkce_code_challenge(code_verifier);
+    let authorize_url =
+        build_authorize_url(provider_config, &redirect_uri, &code_challenge, csrf_state)?;
+
+    app.shell()
+        .open(authorize_url, None)
+        .map_err(|e| e.to_string())?;
+
+    emit_progress(app, "awaiting-redirect");
+    let redirect = code_rx
+        .await
+        .map_err(|_| "oauth callback channel closed".to_string())??;
+
+    if redirect.state.as_deref() != Some(csrf_state) {
+        return Err("oauth redirect state did not match (possible CSRF)".to_string());
+    }
+
+    emit_progress(app, "exchanging-token");
+    let token =
+        exchange_code_for_token(provider_config, &redirect.code, &redirect_uri, code_verifier)
+            .await?;
+
+    let identity = resolve_identity(provider_config, &token.access_token).await?;
+
+    let creds = StoredCredentials {
+        access_token: token.access_token,
+        refresh_token: token.refresh_token,
+        expires_at: token.expires_in.map(|secs| now_unix() + secs),
+        token_type: token.token_type,
+        refresh_config: Some(RefreshConfig {
+            token_url: provider_config.token_url.clone(),
+            client_id: provider_config.client_id.clone(),
+            client_secret: provider_config.client_secret.clone(),
+            refresh_skew_secs: provider_config.refresh_skew_secs,
+        }),
+    };
+    let account = accounts::set_credentials(app, &identity.id, &identity.display_name, &creds)?;
+    let session = ActiveSession {
+        account: account.clone(),
+        credentials: creds,
+    };
+    app_state.set(Some(session.clone()));
+    state::emit_auth_changed(app, Some(&session));
+
+    emit_progress(app, "complete");
+    Ok(account)
+}
+
+fn extract_redirect_params(redirect_url: &str) -> Result<RedirectParams, String> {
+    let url = url::Url::parse(redirect_url).map_err(|e| e.to_string())?;
+    let mut code = None;
+    let mut state = None;
+    for (key, value) in url.query_pairs() {
+        match key.as_ref() {
+            "code" => code = Some(value.into_owned()),
+            "state" => state = Some(value.into_owned()),
+            _ => {}
+        }
+    }
+    let code = code.ok_or_else(|| "redirect did not contain an authorization code".to_string())?;
+    Ok(RedirectParams { code, state })
+}
+
+fn build_authorize_url(
+    config: &OAuthProviderConfig,
+    redirect_uri: &str,
+    code_challenge: &str,
+    state: &str,
+) -> Result<String, String> {
+    let mut url = url::Url::parse(&config.auth_url).map_err(|e| e.to_string())?;
+    {
+        let mut query = url.query_pairs_mut();
+        query.append_pair("client_id", &config.client_id);
+        query.append_pair("redirect_uri", redirect_uri);
+        query.append_pair("response_type", "code");
+        query.append_pair("code_challenge", code_challenge);
+        query.append_pair("code_challenge_method", "S256");
+        query.append_pair("state", state);
+        if let Some(scope) = &config.scope {
+            query.append_pair("scope", scope);
+        }
+    }
+    Ok(url.to_string())
+}
+
+async fn exchange_code_for_token(
+    config: &OAuthProviderConfig,
+    code: &str,
+    redirect_uri: &str,
+    code_verifier: &str,
+) -> Result<TokenResponse, String> {
+    let client = reqwest::Client::new();
+    let mut form = vec![
+        ("grant_type", "authorization_code"),
+        ("code", code),
+        ("redirect_uri", redirect_uri),
+        ("client_id", &config.client_id),
+        ("code_verifier", code_verifier),
+    ];
+    if let Some(secret) = &config.client_secret {
+        form.push(("client_secret", secret));
+    }
+
+    client
+        .post(&config.token_url)
+        .form(&form)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?
+        .error_for_status()
+        .map_err(|e| e.to_string())?
+        .json::<TokenResponse>()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+async fn resolve_identity(
+    config: &OAuthProviderConfig,
+    access_token: &str,
+) -> Result<AccountSummary, String> {
+    let client = reqwest::Client::new();
+    let info = client
+        .get(&config.userinfo_url)
+        .bearer_auth(access_token)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?
+        .error_for_status()
+        .map_err(|e| e.to_string())?
+        .json::<UserInfoResponse>()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(AccountSummary {
+        display_name: info.display_name.unwrap_or_else(|| info.id.clone()),
+        id: info.id,
+        last_used: 0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> OAuthProviderConfig {
+        OAuthProviderConfig {
+            client_id: "client-123".to_string(),
+            client_secret: None,
+            auth_url: "https://provider.example/authorize".to_string(),
+            token_url: "https://provider.example/token".to_string(),
+            userinfo_url: "https://provider.example/userinfo".to_string(),
+            scope: Some("profile".to_string()),
+            refresh_skew_secs: None,
+        }
+    }
+
+    #[test]
+    fn extract_redirect_params_reads_code_and_state() {
+        let params =
+            extract_redirect_params("http://localhost:1234/?code=abc123&state=xyz").unwrap();
+        assert_eq!(params.code, "abc123");
+        assert_eq!(params.state.as_deref(), Some("xyz"));
+    }
+
+    #[test]
+    fn extract_redirect_params_requires_code() {
+        let err = extract_redirect_params("http://localhost:1234/?state=xyz").unwrap_err();
+        assert!(err.contains("authorization code"));
+    }
+
+    #[test]
+    fn extract_redirect_params_rejects_unparseable_url() {
+        assert!(extract_redirect_params("not a url").is_err());
+    }
+
+    #[test]
+    fn build_authorize_url_includes_pkce_and_state() {
+        let url = build_authorize_url(
+            &config(),
+            "http://localhost:4567",
+            "challenge-value",
+            "state-value",
+        )
+        .unwrap();
+
+        assert!(url.starts_with("https://provider.example/authorize?"));
+        assert!(url.contains("client_id=client-123"));
+        assert!(url.contains("redirect_uri=http%3A%2F%2Flocalhost%3A4567"));
+        assert!(url.contains("code_challenge=challenge-value"));
+        assert!(url.contains("code_challenge_method=S256"));
+        assert!(url.contains("state=state-value"));
+        assert!(url.contains("scope=profile"));
+    }
+
+    #[test]
+    fn build_authorize_url_rejects_invalid_auth_url() {
+        let mut cfg = config();
+        cfg.auth_url = "not a url".to_string();
+        assert!(build_authorize_url(&cfg, "http://localhost:4567", "c", "s").is_err());
+    }
+
+    #[test]
+    fn pkce_code_challenge_is_deterministic_and_url_safe() {
+        let challenge_a = pkce_code_challenge("same-verifier");
+        let challenge_b = pkce_code_challenge("same-verifier");
+        assert_eq!(challenge_a, challenge_b);
+        assert!(!challenge_a.contains('+'));
+        assert!(!challenge_a.contains('/'));
+        assert!(!challenge_a.contains('='));
+    }
+
+    #[test]
+    fn random_url_safe_token_has_expected_length_and_is_not_reused() {
+        let a = random_url_safe_token(16);
+        let b = random_url_safe_token(16);
+        assert_ne!(a, b);
+        // 16 bytes base64url-encoded without padding is 22 characters.
+        assert_eq!(a.len(), 22);
+    }
+}
@@ -0,0 +1,151 @@
+use serde::Serialize;
+use tauri::AppHandle;
+
+/// Typed failure from a `SecureStore` backend, surfaced to callers instead
+/// of a raw string so "no backend available" can be told apart from a
+/// backend-specific failure. Implements `Serialize` so it can be returned
+/// directly as a command error and the frontend can match on `kind`.
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", content = "message", rename_all = "snake_case")]
+pub enum SecureStoreError {
+    /// No secure storage backend is compiled in / available on this platform.
+    Unavailable(String),
+    /// The backend itself reported an error (keyring access denied, IPC
+    /// failure talking to the mobile plugin, etc).
+    Backend(String),
+}
+
+impl std::fmt::Display for SecureStoreError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SecureStoreError::Unavailable(msg) => {
+                write!(f, "no secure storage backend available: {msg}")
+            }
+            SecureStoreError::Backend(msg) => write!(f, "secure storage error: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for SecureStoreError {}
+
+impl From<SecureStoreError> for String {
+    fn from(err: SecureStoreError) -> Self {
+        err.to_string()
+    }
+}
+
+/// Platform-agnostic secure key/value storage for auth tokens. Desktop
+/// backs this with the OS keychain via the `keyring` crate; mobile needs
+/// its own implementation since `keyring`'s default providers don't cover
+/// Android/iOS.
+pub trait SecureStore: Send + Sync {
+    fn get(&self, service: &str, account: &str) -> Result<Option<String>, SecureStoreError>;
+    fn set(&self, service: &str, account: &str, value: &str) -> Result<(), SecureStoreError>;
+    fn delete(&self, service: &str, account: &str) -> Result<(), SecureStoreError>;
+}
+
+#[cfg(desktop)]
+mod desktop_store {
+    use super::{SecureStore, SecureStoreError};
+
+    pub struct KeyringStore;
+
+    impl SecureStore for KeyringStore {
+        fn get(&self, service: &str, account: &str) -> Result<Option<String>, SecureStoreError> {
+            let entry = keyring::Entry::new(service, account)
+                .map_err(|e| SecureStoreError::Backend(e.to_string()))?;
+            match entry.get_password() {
+                Ok(value) => Ok(Some(value)),
+                Err(keyring::Error::NoEntry) => Ok(None),
+                Err(err) => Err(SecureStoreError::Backend(err.to_string())),
+            }
+        }
+
+        fn set(&self, service: &str, account: &str, value: &str) -> Result<(), SecureStoreError> {
+            let entry = keyring::Entry::new(service, account)
+                .map_err(|e| SecureStoreError::Backend(e.to_string()))?;
+            entry
+                .set_password(value)
+                .map_err(|e| SecureStoreError::Backend(e.to_string()))
+        }
+
+        fn delete(&self, service: &str, account: &str) -> Result<(), SecureStoreError> {
+            let entry = keyring::Entry::new(service, account)
+                .map_err(|e| SecureStoreError::Backend(e.to_string()))?;
+            match entry.delete_password() {
+                Ok(()) => Ok(()),
+                Err(keyring::Error::NoEntry) => Ok(()),
+                Err(err) => Err(SecureStoreError::Backend(err.to_string())),
+            }
+        }
+    }
+}
+
+#[cfg(mobile)]
+mod mobile_store {
+    use super::{SecureStore, SecureStoreError};
+    use tauri::AppHandle;
+    use tauri_plugin_secure_storage::SecureStorageExt;
+
+    /// Backed by the platform keystore (Android Keystore / iOS Keychain)
+    /// through `tauri-plugin-secure-storage`, since `keyring`'s desktop
+    /// providers aren't available on mobile.
+    pub struct MobileKeystoreStore(pub AppHandle);
+
+    impl SecureStore for MobileKeystoreStore {
+        fn get(&self, service: &str, account: &str) -> Result<Option<String>, SecureStoreError> {
+            self.0
+                .secure_storage()
+                .get(service, account)
+                .map_err(|e| SecureStoreError::Backend(e.to_string()))
+        }
+
+        fn set(&self, service: &str, account: &str, value: &str) -> Result<(), SecureStoreError> {
+            self.0
+                .secure_storage()
+                .set(service, account, value)
+                .map_err(|e| SecureStoreError::Backend(e.to_string()))
+        }
+
+        fn delete(&self, service: &str, account: &str) -> Result<(), SecureStoreError> {
+            self.0
+                .secure_storage()
+                .remove(service, account)
+                .map_err(|e| SecureStoreError::Backend(e.to_string()))
+        }
+    }
+}
+
+/// Resolve the secure storage backend for the current platform.
+#[cfg(desktop)]
+pub fn platform_store(_app: &AppHandle) -> Box<dyn SecureStore> {
+    Box::new(desktop_store::KeyringStore)
+}
+
+#[cfg(mobile)]
+pub fn platform_store(app: &AppHandle) -> Box<dyn SecureStore> {
+    Box::new(mobile_store::MobileKeystoreStore(app.clone()))
+}
+
+#[cfg(not(any(desktop, mobile)))]
+pub fn platform_store(_app: &AppHandle) -> Box<dyn SecureStore> {
+    struct NoStore;
+    impl SecureStore for NoStore {
+        fn get(&self, _service: &str, _account: &str) -> Result<Option<String>, SecureStoreError> {
+            Err(SecureStoreError::Unavailable(
+                "no secure storage backend compiled for this target".into(),
+            ))
+        }
+        fn set(&self, _service: &str, _account: &str, _value: &str) -> Result<(), SecureStoreError> {
+            Err(SecureStoreError::Unavailable(
+                "no secure storage backend compiled for this target".into(),
+            ))
+        }
+        fn delete(&self, _service: &str, _account: &str) -> Result<(), SecureStoreError> {
+            Err(SecureStoreError::Unavailable(
+                "no secure storage backend compiled for this target".into(),
+            ))
+        }
+    }
+    Box::new(NoStore)
+}
@@ -0,0 +1,78 @@
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::accounts::{self, AccountSummary};
+use crate::credentials::{self, StoredCredentials};
+
+const AUTH_CHANGED_EVENT: &str = "auth-changed";
+
+/// The account + credentials currently held in memory, so commands don't
+/// have to re-read keyring on every call.
+#[derive(Debug, Clone)]
+pub struct ActiveSession {
+    pub account: AccountSummary,
+    pub credentials: StoredCredentials,
+}
+
+/// App-wide managed state caching the logged-in session.
+#[derive(Default)]
+pub struct AppState {
+    session: Mutex<Option<ActiveSession>>,
+}
+
+impl AppState {
+    pub fn set(&self, session: Option<ActiveSession>) {
+        *self.session.lock().unwrap() = session;
+    }
+
+    pub fn get(&self) -> Option<ActiveSession> {
+        self.session.lock().unwrap().clone()
+    }
+
+    pub fn is_logged_in(&self) -> bool {
+        self.session.lock().unwrap().is_some()
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct AuthChangedPayload {
+    is_logged_in: bool,
+    account: Option<AccountSummary>,
+}
+
+pub fn emit_auth_changed(app: &AppHandle, session: Option<&ActiveSession>) {
+    let payload = AuthChangedPayload {
+        is_logged_in: session.is_some(),
+        account: session.map(|s| s.account.clone()),
+    };
+    let _ = app.emit(AUTH_CHANGED_EVENT, payload);
+}
+
+#[tauri::command]
+pub fn is_logged_in(state: tauri::State<'_, AppState>) -> bool {
+    state.is_logged_in()
+}
+
+/// Hydrate the in-memory session from keyring on startup, preferring the
+/// most recently used account if one exists.
+pub fn hydrate(app: &AppHandle) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let mut known = accounts::list_accounts(app.clone())?;
+    known.sort_by(|a, b| b.last_used.cmp(&a.last_used));
+
+    let session = known
+        .into_iter()
+        .find_map(|account| match credentials::load(app, &account.id) {
+            Ok(Some(creds)) => Some(Ok(ActiveSession {
+                account,
+                credentials: creds,
+            })),
+            Ok(None) => None,
+            Err(err) => Some(Err(err)),
+        })
+        .transpose()?;
+
+    state.set(session);
+    Ok(())
+}